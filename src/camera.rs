@@ -0,0 +1,78 @@
+use crate::common;
+use crate::ray::Ray;
+use crate::vec3::{self, Point3, Vec3};
+
+// a positionable pinhole camera with adjustable field of view and a thin-lens
+// model for depth-of-field (defocus blur).
+pub struct Camera {
+    origin: Point3,
+    lower_left_corner: Point3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    u: Vec3,
+    v: Vec3,
+    lens_radius: f64,
+    time0: f64, // shutter open time
+    time1: f64, // shutter close time
+}
+
+impl Camera {
+    // `look_from`/`look_at` place and aim the camera, `vup` fixes the roll,
+    // `vfov` is the vertical field of view in degrees, `aperture` is the lens
+    // diameter and `focus_dist` the distance to the plane in sharp focus.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        vup: Vec3,
+        vfov: f64,
+        aspect_ratio: f64,
+        aperture: f64,
+        focus_dist: f64,
+        time0: f64,
+        time1: f64,
+    ) -> Camera {
+        let theta = common::degrees_to_radians(vfov);
+        let h = f64::tan(theta / 2.0);
+        let viewport_height = 2.0 * h;
+        let viewport_width = aspect_ratio * viewport_height;
+
+        // orthonormal basis describing the camera orientation.
+        let w = vec3::unit_vector(look_from - look_at);
+        let u = vec3::unit_vector(vec3::cross(vup, w));
+        let v = vec3::cross(w, u);
+
+        let origin = look_from;
+        let horizontal = focus_dist * viewport_width * u;
+        let vertical = focus_dist * viewport_height * v;
+        let lower_left_corner =
+            origin - horizontal / 2.0 - vertical / 2.0 - focus_dist * w;
+
+        Camera {
+            origin,
+            lower_left_corner,
+            horizontal,
+            vertical,
+            u,
+            v,
+            lens_radius: aperture / 2.0,
+            time0,
+            time1,
+        }
+    }
+
+    // map viewport coordinates (s, t) in [0, 1] to a ray, jittering the origin
+    // across the lens so points off the focal plane blur.
+    pub fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let rd = self.lens_radius * vec3::random_in_unit_disk();
+        let offset = self.u * rd.x() + self.v * rd.y();
+
+        Ray::new(
+            self.origin + offset,
+            self.lower_left_corner + s * self.horizontal + t * self.vertical
+                - self.origin
+                - offset,
+            common::random_double_range(self.time0, self.time1),
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use crate::common;
+use crate::vec3::Vec3;
+use std::io::Write;
+
+// a color is just an RGB vector with components in [0, 1].
+pub type Color = Vec3;
+
+// average the accumulated samples, gamma-correct for gamma=2.0 (i.e. take the
+// square root) and map each channel to a byte. This is the single source of
+// truth for pixel values, shared by every output encoder.
+pub fn to_rgb8(pixel_color: Color, samples_per_pixel: i32) -> [u8; 3] {
+    let scale = 1.0 / samples_per_pixel as f64;
+    let r = f64::sqrt(scale * pixel_color.x());
+    let g = f64::sqrt(scale * pixel_color.y());
+    let b = f64::sqrt(scale * pixel_color.z());
+
+    [
+        (256.0 * common::clamp(r, 0.0, 0.999)) as u8,
+        (256.0 * common::clamp(g, 0.0, 0.999)) as u8,
+        (256.0 * common::clamp(b, 0.0, 0.999)) as u8,
+    ]
+}
+
+// write a single pixel as a line of a P3 ASCII PPM.
+pub fn write_color(out: &mut impl Write, pixel_color: Color, samples_per_pixel: i32) {
+    let [r, g, b] = to_rgb8(pixel_color, samples_per_pixel);
+    writeln!(out, "{} {} {}", r, g, b).expect("writing color failed");
+}
@@ -0,0 +1,28 @@
+use rand::Rng;
+
+// handy constants shared across the tracer.
+pub use std::f64::consts::PI;
+
+pub fn degrees_to_radians(degrees: f64) -> f64 {
+    degrees * PI / 180.0
+}
+
+// returns a random real in [0, 1).
+pub fn random_double() -> f64 {
+    rand::thread_rng().gen()
+}
+
+// returns a random real in [min, max).
+pub fn random_double_range(min: f64, max: f64) -> f64 {
+    min + (max - min) * random_double()
+}
+
+pub fn clamp(x: f64, min: f64, max: f64) -> f64 {
+    if x < min {
+        min
+    } else if x > max {
+        max
+    } else {
+        x
+    }
+}
@@ -0,0 +1,37 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{self, Point3, Vec3};
+use std::sync::Arc;
+
+// the record of a single ray/surface intersection.
+#[derive(Clone, Default)]
+pub struct HitRecord {
+    pub p: Point3,
+    pub normal: Vec3,
+    pub mat: Option<Arc<dyn Material + Send + Sync>>,
+    pub t: f64,
+    pub front_face: bool,
+}
+
+impl HitRecord {
+    pub fn new() -> HitRecord {
+        Default::default()
+    }
+
+    // store a normal that always points against the incident ray, and
+    // remember which side of the surface the ray came from.
+    pub fn set_face_normal(&mut self, r: &Ray, outward_normal: Vec3) {
+        self.front_face = vec3::dot(r.direction(), outward_normal) < 0.0;
+        self.normal = if self.front_face {
+            outward_normal
+        } else {
+            -outward_normal
+        };
+    }
+}
+
+// anything a ray can intersect implements this trait.
+pub trait Hittable {
+    // test whether `r` hits the object within [t_min, t_max]; fills `rec` on success.
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64, rec: &mut HitRecord) -> bool;
+}
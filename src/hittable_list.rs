@@ -0,0 +1,38 @@
+use crate::hittable::{HitRecord, Hittable};
+use crate::ray::Ray;
+
+// a collection of hittable objects, itself hittable.
+#[derive(Default)]
+pub struct HittableList {
+    objects: Vec<Box<dyn Hittable + Send + Sync>>,
+}
+
+impl HittableList {
+    pub fn new() -> HittableList {
+        Default::default()
+    }
+
+    pub fn add(&mut self, object: Box<dyn Hittable + Send + Sync>) {
+        self.objects.push(object);
+    }
+}
+
+impl Hittable for HittableList {
+    // return the closest hit among all objects, shrinking the search
+    // interval as nearer surfaces are found.
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64, rec: &mut HitRecord) -> bool {
+        let mut temp_rec = HitRecord::new();
+        let mut hit_anything = false;
+        let mut closest_so_far = t_max;
+
+        for object in &self.objects {
+            if object.hit(r, t_min, closest_so_far, &mut temp_rec) {
+                hit_anything = true;
+                closest_so_far = temp_rec.t;
+                *rec = temp_rec.clone();
+            }
+        }
+
+        hit_anything
+    }
+}
@@ -3,7 +3,11 @@ mod color;
 mod common;
 mod hittable;
 mod hittable_list;
+mod material;
+mod moving_sphere;
+mod output;
 mod ray;
+mod scene;
 mod sphere;
 mod vec3;
 
@@ -11,49 +15,36 @@ use camera::Camera;
 use color::Color;
 use hittable::{HitRecord, Hittable};
 use hittable_list::HittableList;
+use material::{Dielectric, Lambertian, Metal};
+use moving_sphere::MovingSphere;
+use output::Framebuffer;
 use ray::Ray;
+use scene::RenderSettings;
 use sphere::Sphere;
-use std::io;
+use std::process;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use vec3::{Point3, Vec3};
-/*
-check if the ray hits the sphere
-given a sphere located at the origin:  x^2 + y^2 + z^2 = R^2
-We can check if ray P(t) = A + t*b intersects the sphere by substituting the ray equation into the sphere equation
-We can express a sphere in vector form as dot((P(t) - C), (P(t) - C)) = R^2 where C is the center of the sphere
-and C = (0, 0, 0) for a sphere at the origin. If the ray hits the sphere, there will be a real solution for t.
-We know A, b, and R, so we can substitute them into the equation and solve for t.
-If the discriminant is negative, the ray does not hit the sphere. If the discriminant is zero, the ray grazes the sphere.
-If the discriminant is positive, the ray hits the sphere at two points.
-*/
-fn hit_sphere(center: Point3, radius: f64, r: &Ray) -> f64 {
-    let oc = r.origin() - center; // vector from origin of ray to center of sphere
-    let a = r.direction().length_squared(); // squared length of ray direction
-    let half_b = vec3::dot(oc, r.direction()); // dot product of ray direction and vector from origin of ray to center of sphere
-    let c = oc.length_squared() - radius * radius; // squared length of vector from origin of ray to center of sphere
-    let discriminant = half_b * half_b - 4.0 * a * c; // high school math: b^2 - 4ac
-    if discriminant < 0.0 {
-        -1.0
-    } else {
-        (-half_b - f64::sqrt(discriminant)) / a
-    }
-}
 
+// trace a ray into the world and return the color it sees. On a hit we ask
+// the surface material how to scatter, recursing on the scattered ray and
+// attenuating by its albedo; a miss returns the sky gradient.
 fn ray_color(r: &Ray, world: &dyn Hittable, depth: i32) -> Color {
     let mut rec = HitRecord::new();
 
+    // no light is gathered once we have bounced too many times.
     if depth <= 0 {
         return Color::new(0.0, 0.0, 0.0);
     }
 
-    if world.hit(r, 0.0, f64::INFINITY, &mut rec) {
-        let direction = rec.normal + vec3::random_in_unit_sphere();
-        return 0.5 * ray_color(&Ray::new(rec.p, direction), world, depth - 1);
-    }
-
-    let t = hit_sphere(Point3::new(0.0, 0.0, -2.0), 0.5, r);
-    if t > 0.0 {
-        let n = vec3::unit_vector(r.at(t) - Vec3::new(0.0, 0.0, -2.0)); // normal relative to center of sphere
-        return 0.5 * Color::new(n.x() + 1.0, n.y() + 1.0, n.z() + 1.0); // map normal vector to color
+    if world.hit(r, 0.001, f64::INFINITY, &mut rec) {
+        if let Some(mat) = rec.mat.clone() {
+            if let Some((attenuation, scattered)) = mat.scatter(r, &rec) {
+                return attenuation * ray_color(&scattered, world, depth - 1);
+            }
+        }
+        return Color::new(0.0, 0.0, 0.0);
     }
 
     let unit_direction = vec3::unit_vector(r.direction());
@@ -61,36 +52,194 @@ fn ray_color(r: &Ray, world: &dyn Hittable, depth: i32) -> Color {
     (1.0 - t) * Color::new(1.0, 1.0, 1.0) + t * Color::new(0.5, 0.7, 1.0) // linear blend
 }
 
-fn main() {
+// the built-in scene, used when no scene file is supplied on the command line.
+fn default_scene() -> (HittableList, Camera, RenderSettings) {
     const ASPECT_RATIO: f64 = 16.0 / 9.0;
-    const IMAGE_WIDTH: i32 = 400;
-    const IMAGE_HEIGHT: i32 = (IMAGE_WIDTH as f64 / ASPECT_RATIO) as i32;
-    const SAMPLES_PER_PIXEL: i32 = 100;
-    const MAX_DEPTH: i32 = 50;
+    let settings = RenderSettings {
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        aspect_ratio: ASPECT_RATIO,
+    };
 
-    // world
     let mut world = HittableList::new();
-    world.add(Box::new(Sphere::new(Point3::new(0.0, 0.0, -1.0), 0.5)));
-    world.add(Box::new(Sphere::new(Point3::new(0.0, -100.5, -1.0), 100.0)));
 
-    //camera
+    let material_ground = Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.0)));
+    let material_center = Arc::new(Lambertian::new(Color::new(0.1, 0.2, 0.5)));
+    let material_left = Arc::new(Dielectric::new(1.5));
+    let material_right = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.0));
 
-    let cam = Camera::new();
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        material_ground,
+    )));
+    // the center sphere bounces during the shutter interval to show motion blur.
+    let center0 = Point3::new(0.0, 0.0, -1.0);
+    let center1 = center0 + Vec3::new(0.0, 0.25, 0.0);
+    world.add(Box::new(MovingSphere::new(
+        center0,
+        center1,
+        0.0,
+        1.0,
+        0.5,
+        material_center,
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(-1.0, 0.0, -1.0),
+        0.5,
+        material_left,
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(1.0, 0.0, -1.0),
+        0.5,
+        material_right,
+    )));
 
-    print!("P3\n{} {}\n255\n", IMAGE_WIDTH, IMAGE_HEIGHT);
+    let look_from = Point3::new(3.0, 3.0, 2.0);
+    let look_at = Point3::new(0.0, 0.0, -1.0);
+    let dist_to_focus = (look_from - look_at).length();
+    let cam = Camera::new(
+        look_from,
+        look_at,
+        Vec3::new(0.0, 1.0, 0.0),
+        20.0,
+        ASPECT_RATIO,
+        2.0,
+        dist_to_focus,
+        0.0,
+        1.0,
+    );
+
+    (world, cam, settings)
+}
 
-    for j in (0..IMAGE_HEIGHT).rev() {
-        eprint!("\rScanlines remaining: {}", j);
-        for i in 0..IMAGE_WIDTH {
-            let mut pixel_color = Color::new(0.0, 0.0, 0.0);
-            for _ in 0..SAMPLES_PER_PIXEL {
-                let u = (i as f64 + common::random_double()) / (IMAGE_WIDTH - 1) as f64;
-                let v = (j as f64 + common::random_double()) / (IMAGE_HEIGHT - 1) as f64;
-                let r = cam.get_ray(u, v);
-                pixel_color += ray_color(&r, &world, MAX_DEPTH);
+fn main() {
+    // a scene file may be passed as a positional argument; otherwise render the
+    // built-in scene.
+    let (world, cam, settings) = match scene_path() {
+        Some(path) => match scene::load(&path) {
+            Ok(scene) => scene.build(),
+            Err(e) => {
+                eprintln!("error loading scene '{}': {}", path, e);
+                process::exit(1);
             }
-            color::write_color(&mut io::stdout(), pixel_color, SAMPLES_PER_PIXEL);
-        }
+        },
+        None => default_scene(),
+    };
+
+    let image_width = settings.image_width;
+    let image_height = (image_width as f64 / settings.aspect_ratio) as i32;
+    let samples_per_pixel = settings.samples_per_pixel;
+    let max_depth = settings.max_depth;
+
+    // share the immutable world and camera across worker threads.
+    let world: Arc<dyn Hittable + Send + Sync> = Arc::new(world);
+    let cam = Arc::new(cam);
+    let num_threads = num_threads();
+
+    // the work queue hands out one scanline index at a time; results flow back
+    // to this thread, which reassembles them in order.
+    let (row_tx, row_rx) = mpsc::channel::<i32>();
+    for j in (0..image_height).rev() {
+        row_tx.send(j).unwrap();
+    }
+    drop(row_tx);
+    let row_rx = Arc::new(Mutex::new(row_rx));
+
+    let (result_tx, result_rx) = mpsc::channel::<(i32, Vec<Color>)>();
+
+    for _ in 0..num_threads {
+        let row_rx = Arc::clone(&row_rx);
+        let result_tx = result_tx.clone();
+        let world = Arc::clone(&world);
+        let cam = Arc::clone(&cam);
+        thread::spawn(move || loop {
+            // pull the next scanline, releasing the lock before rendering.
+            let j = {
+                let rx = row_rx.lock().unwrap();
+                rx.recv()
+            };
+            let j = match j {
+                Ok(j) => j,
+                Err(_) => break, // queue drained
+            };
+
+            let mut row = Vec::with_capacity(image_width as usize);
+            for i in 0..image_width {
+                let mut pixel_color = Color::new(0.0, 0.0, 0.0);
+                for _ in 0..samples_per_pixel {
+                    let u = (i as f64 + common::random_double()) / (image_width - 1) as f64;
+                    let v = (j as f64 + common::random_double()) / (image_height - 1) as f64;
+                    let r = cam.get_ray(u, v);
+                    pixel_color += ray_color(&r, world.as_ref(), max_depth);
+                }
+                row.push(pixel_color);
+            }
+            result_tx.send((j, row)).unwrap();
+        });
+    }
+    drop(result_tx);
+
+    // collect finished rows into the framebuffer as they arrive; `j` counts up
+    // from the bottom, so it maps to framebuffer row `height - 1 - j`.
+    let mut framebuffer =
+        Framebuffer::new(image_width as usize, image_height as usize, samples_per_pixel);
+    let mut remaining = image_height;
+    for (j, row) in result_rx {
+        framebuffer.set_row((image_height - 1 - j) as usize, &row);
+        remaining -= 1;
+        eprint!("\rScanlines remaining: {}", remaining);
     }
     eprint!("\nDone.\n");
+
+    if let Err(e) = framebuffer.write(output_path().as_deref()) {
+        eprintln!("error writing output: {}", e);
+        process::exit(1);
+    }
+}
+
+// the first positional argument (a scene file path), skipping flags that take
+// a value (`--threads N`, `-o PATH`).
+fn scene_path() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--threads" || arg == "-o" {
+            args.next(); // consume the flag's value
+        } else {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+// the value of the `-o PATH` flag, if present. Absent means write PPM to stdout.
+fn output_path() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "-o" {
+            return args.next();
+        }
+        if arg == "--threads" {
+            args.next(); // skip the thread count
+        }
+    }
+    None
+}
+
+// the worker count, from a `--threads N` argument or the available parallelism.
+fn num_threads() -> usize {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--threads" {
+            if let Some(n) = args.next().and_then(|v| v.parse::<usize>().ok()) {
+                if n > 0 {
+                    return n;
+                }
+            }
+        }
+    }
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
 }
@@ -0,0 +1,116 @@
+use crate::color::Color;
+use crate::common;
+use crate::hittable::HitRecord;
+use crate::ray::Ray;
+use crate::vec3;
+
+// how a surface scatters an incoming ray. `scatter` returns the attenuation
+// color and the scattered ray, or None when the ray is fully absorbed.
+pub trait Material {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)>;
+}
+
+// a matte surface that scatters diffusely around the surface normal.
+pub struct Lambertian {
+    albedo: Color,
+}
+
+impl Lambertian {
+    pub fn new(albedo: Color) -> Lambertian {
+        Lambertian { albedo }
+    }
+}
+
+impl Material for Lambertian {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
+        let mut scatter_direction = rec.normal + vec3::random_unit_vector();
+
+        // catch degenerate scatter directions that would produce NaNs later.
+        if scatter_direction.near_zero() {
+            scatter_direction = rec.normal;
+        }
+
+        let scattered = Ray::new(rec.p, vec3::unit_vector(scatter_direction), r_in.time());
+        Some((self.albedo, scattered))
+    }
+}
+
+// a reflective surface, optionally roughened by `fuzz`.
+pub struct Metal {
+    albedo: Color,
+    fuzz: f64,
+}
+
+impl Metal {
+    pub fn new(albedo: Color, fuzz: f64) -> Metal {
+        Metal {
+            albedo,
+            fuzz: if fuzz < 1.0 { fuzz } else { 1.0 },
+        }
+    }
+}
+
+impl Material for Metal {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
+        let reflected = vec3::reflect(vec3::unit_vector(r_in.direction()), rec.normal);
+        let scattered = Ray::new(
+            rec.p,
+            reflected + self.fuzz * vec3::random_in_unit_sphere(),
+            r_in.time(),
+        );
+
+        // absorb rays that scatter below the surface.
+        if vec3::dot(scattered.direction(), rec.normal) > 0.0 {
+            Some((self.albedo, scattered))
+        } else {
+            None
+        }
+    }
+}
+
+// a clear material (glass, water) that refracts using Snell's law.
+pub struct Dielectric {
+    ir: f64, // index of refraction
+}
+
+impl Dielectric {
+    pub fn new(index_of_refraction: f64) -> Dielectric {
+        Dielectric {
+            ir: index_of_refraction,
+        }
+    }
+
+    // Schlick's polynomial approximation of reflectance.
+    fn reflectance(cosine: f64, ref_idx: f64) -> f64 {
+        let mut r0 = (1.0 - ref_idx) / (1.0 + ref_idx);
+        r0 = r0 * r0;
+        r0 + (1.0 - r0) * f64::powi(1.0 - cosine, 5)
+    }
+}
+
+impl Material for Dielectric {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
+        let attenuation = Color::new(1.0, 1.0, 1.0);
+        let refraction_ratio = if rec.front_face {
+            1.0 / self.ir
+        } else {
+            self.ir
+        };
+
+        let unit_direction = vec3::unit_vector(r_in.direction());
+        let cos_theta = f64::min(vec3::dot(-unit_direction, rec.normal), 1.0);
+        let sin_theta = f64::sqrt(1.0 - cos_theta * cos_theta);
+
+        // total internal reflection, or a probabilistic Fresnel reflection.
+        let cannot_refract = refraction_ratio * sin_theta > 1.0;
+        let direction = if cannot_refract
+            || Dielectric::reflectance(cos_theta, refraction_ratio) > common::random_double()
+        {
+            vec3::reflect(unit_direction, rec.normal)
+        } else {
+            vec3::refract(unit_direction, rec.normal, refraction_ratio)
+        };
+
+        Some((attenuation, Ray::new(rec.p, direction, r_in.time())))
+    }
+}
@@ -0,0 +1,74 @@
+use crate::color::{self, Color};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+// a full-image buffer of accumulated pixel colors. Rows may be filled in any
+// order (needed by the parallel renderer); encoding happens once the whole
+// image is present. Row 0 is the top scanline.
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    samples_per_pixel: i32,
+    pixels: Vec<Color>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize, samples_per_pixel: i32) -> Framebuffer {
+        Framebuffer {
+            width,
+            height,
+            samples_per_pixel,
+            pixels: vec![Color::default(); width * height],
+        }
+    }
+
+    // store a whole scanline; `row` is measured from the top of the image.
+    pub fn set_row(&mut self, row: usize, colors: &[Color]) {
+        let start = row * self.width;
+        self.pixels[start..start + self.width].copy_from_slice(colors);
+    }
+
+    // encode the framebuffer to `path`, choosing the format from its extension
+    // (png, jpg/jpeg or ppm). `None` writes a PPM to stdout.
+    pub fn write(&self, path: Option<&str>) -> io::Result<()> {
+        match path {
+            None => self.write_ppm(&mut io::stdout().lock()),
+            Some(path) => match extension(path).as_deref() {
+                Some("ppm") => self.write_ppm(&mut BufWriter::new(File::create(path)?)),
+                Some("png") | Some("jpg") | Some("jpeg") => self.write_image(path),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unsupported output format for '{}'", path),
+                )),
+            },
+        }
+    }
+
+    fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "P3\n{} {}\n255", self.width, self.height)?;
+        for &pixel in &self.pixels {
+            color::write_color(out, pixel, self.samples_per_pixel);
+        }
+        Ok(())
+    }
+
+    // encode via the `image` crate, which picks PNG/JPEG from the extension.
+    fn write_image(&self, path: &str) -> io::Result<()> {
+        let mut buffer = image::ImageBuffer::new(self.width as u32, self.height as u32);
+        for (x, y, pixel) in buffer.enumerate_pixels_mut() {
+            let color = self.pixels[y as usize * self.width + x as usize];
+            *pixel = image::Rgb(color::to_rgb8(color, self.samples_per_pixel));
+        }
+        buffer
+            .save(path)
+            .map_err(io::Error::other)
+    }
+}
+
+fn extension(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+}
@@ -2,18 +2,21 @@ use crate::vec3::{Point3, Vec3};
 
 // consider a ray P(t) = A + t*b* where P(t) is a 3D
 // point on a ray at time t. A is the ray origin and b is the ray direction.
+// `tm` records the instant the ray was cast, used for motion blur.
 
 #[derive(Default)]
 pub struct Ray {
     orig: Point3,
     dir: Vec3,
+    tm: f64,
 }
 
 impl Ray {
-    pub fn new(origin: Point3, direction: Vec3) -> Ray {
+    pub fn new(origin: Point3, direction: Vec3, time: f64) -> Ray {
         Ray {
             orig: origin,
             dir: direction,
+            tm: time,
         }
     }
 
@@ -25,7 +28,11 @@ impl Ray {
         self.dir
     }
 
+    pub fn time(&self) -> f64 {
+        self.tm
+    }
+
     pub fn at(&self, t: f64) -> Point3 { // P(t) = A + t*b
         self.orig + self.dir * t
     }
-}
\ No newline at end of file
+}
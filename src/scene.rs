@@ -0,0 +1,401 @@
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::hittable_list::HittableList;
+use crate::material::{Dielectric, Lambertian, Material, Metal};
+use crate::moving_sphere::MovingSphere;
+use crate::sphere::Sphere;
+use crate::vec3::{Point3, Vec3};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+// image and sampling parameters that are not part of the camera itself.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RenderSettings {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    pub aspect_ratio: f64,
+}
+
+// the material attached to an object in a scene file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MaterialSpec {
+    Lambertian { albedo: Color },
+    Metal { albedo: Color, fuzz: f64 },
+    Dielectric { ior: f64 },
+}
+
+impl MaterialSpec {
+    fn build(&self) -> Arc<dyn Material + Send + Sync> {
+        match *self {
+            MaterialSpec::Lambertian { albedo } => Arc::new(Lambertian::new(albedo)),
+            MaterialSpec::Metal { albedo, fuzz } => Arc::new(Metal::new(albedo, fuzz)),
+            MaterialSpec::Dielectric { ior } => Arc::new(Dielectric::new(ior)),
+        }
+    }
+}
+
+// a single primitive in a scene file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ObjectSpec {
+    Sphere {
+        center: Point3,
+        radius: f64,
+        material: MaterialSpec,
+    },
+    MovingSphere {
+        center0: Point3,
+        center1: Point3,
+        time0: f64,
+        time1: f64,
+        radius: f64,
+        material: MaterialSpec,
+    },
+}
+
+// a complete, fully-parsed scene description. `focus_dist` is `None` when the
+// file requests automatic focus (the distance from `look_from` to `look_at`).
+#[derive(Clone, PartialEq, Debug)]
+pub struct Scene {
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub vup: Vec3,
+    pub vfov: f64,
+    pub aperture: f64,
+    pub focus_dist: Option<f64>,
+    pub settings: RenderSettings,
+    pub objects: Vec<ObjectSpec>,
+}
+
+impl Scene {
+    // resolve the description into renderable objects, a camera and settings.
+    pub fn build(&self) -> (HittableList, Camera, RenderSettings) {
+        let mut world = HittableList::new();
+        for object in &self.objects {
+            match *object {
+                ObjectSpec::Sphere {
+                    center,
+                    radius,
+                    material,
+                } => {
+                    world.add(Box::new(Sphere::new(center, radius, material.build())));
+                }
+                ObjectSpec::MovingSphere {
+                    center0,
+                    center1,
+                    time0,
+                    time1,
+                    radius,
+                    material,
+                } => {
+                    world.add(Box::new(MovingSphere::new(
+                        center0,
+                        center1,
+                        time0,
+                        time1,
+                        radius,
+                        material.build(),
+                    )));
+                }
+            }
+        }
+
+        let focus_dist = self
+            .focus_dist
+            .unwrap_or_else(|| (self.look_from - self.look_at).length());
+        let camera = Camera::new(
+            self.look_from,
+            self.look_at,
+            self.vup,
+            self.vfov,
+            self.settings.aspect_ratio,
+            self.aperture,
+            focus_dist,
+            0.0,
+            1.0,
+        );
+
+        (world, camera, self.settings)
+    }
+}
+
+// read and parse a scene file from disk.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Scene> {
+    let text = fs::read_to_string(path)?;
+    parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// parse a scene from its textual form. Blank lines and lines beginning with
+// `#` are ignored; every other line is a directive whose first token names it.
+pub fn parse(text: &str) -> Result<Scene, String> {
+    let mut look_from = None;
+    let mut look_at = None;
+    let mut vup = Vec3::new(0.0, 1.0, 0.0);
+    let mut vfov = None;
+    let mut aperture = 0.0;
+    let mut focus_dist = None;
+    let mut image_width = None;
+    let mut samples_per_pixel = None;
+    let mut max_depth = None;
+    let mut aspect_ratio = 16.0 / 9.0;
+    let mut objects = Vec::new();
+
+    for (n, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {}", n + 1, msg);
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap();
+        let rest: Vec<&str> = tokens.collect();
+
+        match keyword {
+            "look_from" => look_from = Some(parse_vec3(&rest).map_err(|e| err(&e))?),
+            "look_at" => look_at = Some(parse_vec3(&rest).map_err(|e| err(&e))?),
+            "vup" => vup = parse_vec3(&rest).map_err(|e| err(&e))?,
+            "vfov" => vfov = Some(parse_f64(rest.first().copied(), "vfov").map_err(|e| err(&e))?),
+            "aperture" => {
+                aperture = parse_f64(rest.first().copied(), "aperture").map_err(|e| err(&e))?
+            }
+            "focus_dist" => {
+                focus_dist = match rest.first().copied() {
+                    Some("auto") => None,
+                    other => Some(parse_f64(other, "focus_dist").map_err(|e| err(&e))?),
+                }
+            }
+            "aspect_ratio" => {
+                aspect_ratio =
+                    parse_f64(rest.first().copied(), "aspect_ratio").map_err(|e| err(&e))?
+            }
+            "image_width" => {
+                image_width = Some(parse_i32(rest.first().copied(), "image_width").map_err(|e| err(&e))?)
+            }
+            "samples_per_pixel" => {
+                samples_per_pixel = Some(
+                    parse_i32(rest.first().copied(), "samples_per_pixel").map_err(|e| err(&e))?,
+                )
+            }
+            "max_depth" => {
+                max_depth = Some(parse_i32(rest.first().copied(), "max_depth").map_err(|e| err(&e))?)
+            }
+            "sphere" => {
+                let center = parse_vec3(rest.get(0..3).unwrap_or_default()).map_err(|e| err(&e))?;
+                let radius = parse_f64(rest.get(3).copied(), "radius").map_err(|e| err(&e))?;
+                let material = parse_material(&rest[4.min(rest.len())..]).map_err(|e| err(&e))?;
+                objects.push(ObjectSpec::Sphere {
+                    center,
+                    radius,
+                    material,
+                });
+            }
+            "moving_sphere" => {
+                let center0 =
+                    parse_vec3(rest.get(0..3).unwrap_or_default()).map_err(|e| err(&e))?;
+                let center1 =
+                    parse_vec3(rest.get(3..6).unwrap_or_default()).map_err(|e| err(&e))?;
+                let time0 = parse_f64(rest.get(6).copied(), "time0").map_err(|e| err(&e))?;
+                let time1 = parse_f64(rest.get(7).copied(), "time1").map_err(|e| err(&e))?;
+                let radius = parse_f64(rest.get(8).copied(), "radius").map_err(|e| err(&e))?;
+                let material = parse_material(&rest[9.min(rest.len())..]).map_err(|e| err(&e))?;
+                objects.push(ObjectSpec::MovingSphere {
+                    center0,
+                    center1,
+                    time0,
+                    time1,
+                    radius,
+                    material,
+                });
+            }
+            other => return Err(err(&format!("unknown directive '{}'", other))),
+        }
+    }
+
+    let settings = RenderSettings {
+        image_width: image_width.ok_or_else(|| "missing 'image_width'".to_string())?,
+        samples_per_pixel: samples_per_pixel
+            .ok_or_else(|| "missing 'samples_per_pixel'".to_string())?,
+        max_depth: max_depth.ok_or_else(|| "missing 'max_depth'".to_string())?,
+        aspect_ratio,
+    };
+
+    Ok(Scene {
+        look_from: look_from.ok_or_else(|| "missing 'look_from'".to_string())?,
+        look_at: look_at.ok_or_else(|| "missing 'look_at'".to_string())?,
+        vup,
+        vfov: vfov.ok_or_else(|| "missing 'vfov'".to_string())?,
+        aperture,
+        focus_dist,
+        settings,
+        objects,
+    })
+}
+
+fn parse_f64(token: Option<&str>, what: &str) -> Result<f64, String> {
+    token
+        .ok_or_else(|| format!("missing value for '{}'", what))?
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number for '{}'", what))
+}
+
+fn parse_i32(token: Option<&str>, what: &str) -> Result<i32, String> {
+    token
+        .ok_or_else(|| format!("missing value for '{}'", what))?
+        .parse::<i32>()
+        .map_err(|_| format!("invalid integer for '{}'", what))
+}
+
+fn parse_vec3(tokens: &[&str]) -> Result<Vec3, String> {
+    if tokens.len() < 3 {
+        return Err("expected three numbers".to_string());
+    }
+    Ok(Vec3::new(
+        parse_f64(Some(tokens[0]), "x")?,
+        parse_f64(Some(tokens[1]), "y")?,
+        parse_f64(Some(tokens[2]), "z")?,
+    ))
+}
+
+fn parse_material(tokens: &[&str]) -> Result<MaterialSpec, String> {
+    let kind = tokens
+        .first()
+        .copied()
+        .ok_or_else(|| "missing material".to_string())?;
+    let args = &tokens[1..];
+    match kind {
+        "lambertian" => Ok(MaterialSpec::Lambertian {
+            albedo: parse_vec3(args)?,
+        }),
+        "metal" => Ok(MaterialSpec::Metal {
+            albedo: parse_vec3(args.get(0..3).unwrap_or_default())?,
+            fuzz: parse_f64(args.get(3).copied(), "fuzz")?,
+        }),
+        "dielectric" => Ok(MaterialSpec::Dielectric {
+            ior: parse_f64(args.first().copied(), "ior")?,
+        }),
+        other => Err(format!("unknown material '{}'", other)),
+    }
+}
+
+// `Display` emits the canonical textual form, the inverse of `parse`.
+impl fmt::Display for Scene {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "look_from {}", fmt_vec3(self.look_from))?;
+        writeln!(f, "look_at {}", fmt_vec3(self.look_at))?;
+        writeln!(f, "vup {}", fmt_vec3(self.vup))?;
+        writeln!(f, "vfov {}", self.vfov)?;
+        writeln!(f, "aperture {}", self.aperture)?;
+        match self.focus_dist {
+            Some(d) => writeln!(f, "focus_dist {}", d)?,
+            None => writeln!(f, "focus_dist auto")?,
+        }
+        writeln!(f, "aspect_ratio {}", self.settings.aspect_ratio)?;
+        writeln!(f, "image_width {}", self.settings.image_width)?;
+        writeln!(f, "samples_per_pixel {}", self.settings.samples_per_pixel)?;
+        writeln!(f, "max_depth {}", self.settings.max_depth)?;
+        for object in &self.objects {
+            writeln!(f, "{}", object)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ObjectSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjectSpec::Sphere {
+                center,
+                radius,
+                material,
+            } => write!(f, "sphere {} {} {}", fmt_vec3(*center), radius, material),
+            ObjectSpec::MovingSphere {
+                center0,
+                center1,
+                time0,
+                time1,
+                radius,
+                material,
+            } => write!(
+                f,
+                "moving_sphere {} {} {} {} {} {}",
+                fmt_vec3(*center0),
+                fmt_vec3(*center1),
+                time0,
+                time1,
+                radius,
+                material
+            ),
+        }
+    }
+}
+
+impl fmt::Display for MaterialSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MaterialSpec::Lambertian { albedo } => write!(f, "lambertian {}", fmt_vec3(*albedo)),
+            MaterialSpec::Metal { albedo, fuzz } => {
+                write!(f, "metal {} {}", fmt_vec3(*albedo), fuzz)
+            }
+            MaterialSpec::Dielectric { ior } => write!(f, "dielectric {}", ior),
+        }
+    }
+}
+
+fn fmt_vec3(v: Vec3) -> String {
+    format!("{} {} {}", v.x(), v.y(), v.z())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+look_from 3 3 2
+look_at 0 0 -1
+vup 0 1 0
+vfov 20
+aperture 2
+focus_dist auto
+aspect_ratio 1.5
+image_width 400
+samples_per_pixel 100
+max_depth 50
+sphere 0 -100.5 -1 100 lambertian 0.8 0.8 0
+sphere -1 0 -1 0.5 dielectric 1.5
+sphere 1 0 -1 0.5 metal 0.8 0.6 0.2 0
+moving_sphere 0 0 -1 0 0.25 -1 0 1 0.5 lambertian 0.1 0.2 0.5
+";
+
+    #[test]
+    fn parses_expected_contents() {
+        let scene = parse(SAMPLE).expect("sample should parse");
+        assert_eq!(scene.vfov, 20.0);
+        assert_eq!(scene.focus_dist, None);
+        assert_eq!(scene.settings.image_width, 400);
+        assert_eq!(scene.objects.len(), 4);
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let scene = parse(SAMPLE).expect("sample should parse");
+        let reparsed = parse(&scene.to_string()).expect("serialized scene should parse");
+        assert!(scene == reparsed);
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() {
+        let scene = parse(SAMPLE).unwrap();
+        let annotated = format!("# a comment\n\n{}\n   # trailing\n", SAMPLE);
+        assert!(parse(&annotated).unwrap() == scene);
+    }
+
+    #[test]
+    fn reports_unknown_directive() {
+        let err = parse("wobble 1 2 3\n").unwrap_err();
+        assert!(err.contains("unknown directive"));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{self, Point3};
+use std::sync::Arc;
+
+// a sphere defined by its center, radius and surface material.
+pub struct Sphere {
+    center: Point3,
+    radius: f64,
+    mat: Arc<dyn Material + Send + Sync>,
+}
+
+impl Sphere {
+    pub fn new(center: Point3, radius: f64, mat: Arc<dyn Material + Send + Sync>) -> Sphere {
+        Sphere {
+            center,
+            radius,
+            mat,
+        }
+    }
+}
+
+impl Hittable for Sphere {
+    // solve the quadratic t^2*b.b + 2t*b.(A-C) + (A-C).(A-C) - r^2 = 0
+    // using the half-b simplification, then keep the nearest root in range.
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64, rec: &mut HitRecord) -> bool {
+        let oc = r.origin() - self.center;
+        let a = r.direction().length_squared();
+        let half_b = vec3::dot(oc, r.direction());
+        let c = oc.length_squared() - self.radius * self.radius;
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return false;
+        }
+        let sqrtd = f64::sqrt(discriminant);
+
+        // find the nearest root that lies in the acceptable range.
+        let mut root = (-half_b - sqrtd) / a;
+        if root < t_min || t_max < root {
+            root = (-half_b + sqrtd) / a;
+            if root < t_min || t_max < root {
+                return false;
+            }
+        }
+
+        rec.t = root;
+        rec.p = r.at(rec.t);
+        let outward_normal = (rec.p - self.center) / self.radius;
+        rec.set_face_normal(r, outward_normal);
+        rec.mat = Some(self.mat.clone());
+
+        true
+    }
+}